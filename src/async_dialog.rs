@@ -0,0 +1,236 @@
+//! Non-blocking, awaitable variants of the dialog functions in the crate root.
+//!
+//! Every dialog in [`crate`] blocks the calling thread until the user
+//! dismisses it, which makes them unusable from inside an event loop or an
+//! async runtime. Each function here spawns the equivalent blocking call on
+//! a dedicated [`std::thread`] and returns a [`DialogHandle`] that can be
+//! polled non-blockingly via [`DialogHandle::poll`]/[`DialogHandle::try_recv`]
+//! or driven to completion by `.await`ing it directly, since it implements
+//! [`Future`].
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, JoinHandle};
+
+use crate::{
+    color_chooser_dialog, input_box, list_dialog, message_box, open_file_dialog,
+    open_file_dialog_multi, password_box, save_file_dialog, save_file_dialog_with_filter,
+    select_folder_dialog, BoxButton, DefaultColorValue, Icon, MessageBox,
+};
+
+/// A handle to a dialog running on a background thread.
+///
+/// The underlying FFI call cannot be cancelled once started, so dropping a
+/// `DialogHandle` joins its thread and blocks until the user dismisses the
+/// dialog; this only matters if the handle is dropped before it resolves.
+pub struct DialogHandle<T> {
+    receiver: Receiver<T>,
+    waker: Arc<Mutex<Option<Waker>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<T: Send + 'static> DialogHandle<T> {
+    fn spawn<F>(f: F) -> DialogHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let waker: Arc<Mutex<Option<Waker>>> = Arc::new(Mutex::new(None));
+        let waker_thread = waker.clone();
+
+        let thread = thread::spawn(move || {
+            let result = f();
+            let _ = sender.send(result);
+
+            if let Some(waker) = waker_thread.lock().unwrap().take() {
+                waker.wake();
+            }
+        });
+
+        DialogHandle { receiver, waker, thread: Some(thread) }
+    }
+
+    /// Returns the dialog's result if the user has already dismissed it,
+    /// without blocking the calling thread.
+    pub fn poll(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Non-blocking check for a completed dialog. Equivalent to [`Self::poll`].
+    pub fn try_recv(&self) -> Option<T> {
+        self.poll()
+    }
+}
+
+impl<T: Send + 'static> Future for DialogHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let this = self.get_mut();
+
+        // Register the waker *before* the deciding `try_recv`, so a result sent between an
+        // earlier empty check and this registration can't be missed: the worker thread only
+        // wakes a waker it finds already in place (see `spawn`), so if `send` raced ahead of
+        // us, the re-check below still observes it.
+        *this.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        match this.receiver.try_recv() {
+            Ok(value) => Poll::Ready(value),
+            Err(TryRecvError::Empty) => Poll::Pending,
+            Err(TryRecvError::Disconnected) => {
+                // The only way the sender drops without sending is the wrapped dialog closure
+                // panicking (e.g. an unexpected FFI return hitting `unimplemented!()`). Propagate
+                // that panic to the awaiting task instead of asserting something that can happen.
+                let payload = this.thread.take().and_then(|thread| thread.join().err());
+
+                match payload {
+                    Some(payload) => std::panic::resume_unwind(payload),
+                    None => panic!("dialog thread exited without sending a result or panicking"),
+                }
+            }
+        }
+    }
+}
+
+impl<T> Drop for DialogHandle<T> {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Non-blocking variant of [`message_box`].
+pub fn message_box_async(kind: MessageBox, title: &str, message: &str, icon: Option<Icon>, default_button: Option<BoxButton>) -> DialogHandle<BoxButton> {
+    let title = title.to_owned();
+    let message = message.to_owned();
+
+    DialogHandle::spawn(move || message_box(kind, &title, &message, icon, default_button))
+}
+
+/// Non-blocking variant of [`input_box`].
+pub fn input_box_async(title: &str, message: &str, default: Option<&str>) -> DialogHandle<Option<String>> {
+    let title = title.to_owned();
+    let message = message.to_owned();
+    let default = default.map(|default| default.to_owned());
+
+    DialogHandle::spawn(move || input_box(&title, &message, default.as_deref()))
+}
+
+/// Non-blocking variant of [`password_box`].
+pub fn password_box_async(title: &str, message: &str) -> DialogHandle<Option<String>> {
+    let title = title.to_owned();
+    let message = message.to_owned();
+
+    DialogHandle::spawn(move || password_box(&title, &message))
+}
+
+/// Owns a copy of a `(patterns, description)` file filter so it can be
+/// moved onto a background thread.
+type OwnedFilter = (Vec<String>, String);
+
+fn to_owned_filter(filter: Option<(&[&str], &str)>) -> Option<OwnedFilter> {
+    filter.map(|(patterns, description)| (patterns.iter().map(|s| (*s).to_owned()).collect(), description.to_owned()))
+}
+
+fn borrow_filter(filter: &Option<OwnedFilter>) -> Option<(Vec<&str>, &str)> {
+    filter.as_ref().map(|(patterns, description)| (patterns.iter().map(String::as_str).collect(), description.as_str()))
+}
+
+/// Non-blocking variant of [`save_file_dialog`].
+pub fn save_file_dialog_async(title: &str, path: &str) -> DialogHandle<Option<String>> {
+    let title = title.to_owned();
+    let path = path.to_owned();
+
+    DialogHandle::spawn(move || save_file_dialog(&title, &path))
+}
+
+/// Non-blocking variant of [`save_file_dialog_with_filter`].
+pub fn save_file_dialog_with_filter_async(title: &str, path: &str, filter_patterns: &[&str], description: &str) -> DialogHandle<Option<String>> {
+    let title = title.to_owned();
+    let path = path.to_owned();
+    let filter_patterns: Vec<String> = filter_patterns.iter().map(|s| (*s).to_owned()).collect();
+    let description = description.to_owned();
+
+    DialogHandle::spawn(move || {
+        let filter_patterns: Vec<&str> = filter_patterns.iter().map(String::as_str).collect();
+        save_file_dialog_with_filter(&title, &path, &filter_patterns, &description)
+    })
+}
+
+/// Non-blocking variant of [`open_file_dialog`].
+pub fn open_file_dialog_async(title: &str, path: &str, filter: Option<(&[&str], &str)>) -> DialogHandle<Option<String>> {
+    let title = title.to_owned();
+    let path = path.to_owned();
+    let filter = to_owned_filter(filter);
+
+    DialogHandle::spawn(move || {
+        let filter = borrow_filter(&filter);
+        open_file_dialog(&title, &path, filter.as_ref().map(|(patterns, description)| (patterns.as_slice(), *description)))
+    })
+}
+
+/// Non-blocking variant of [`open_file_dialog_multi`].
+pub fn open_file_dialog_multi_async(title: &str, path: &str, filter: Option<(&[&str], &str)>) -> DialogHandle<Option<Vec<String>>> {
+    let title = title.to_owned();
+    let path = path.to_owned();
+    let filter = to_owned_filter(filter);
+
+    DialogHandle::spawn(move || {
+        let filter = borrow_filter(&filter);
+        open_file_dialog_multi(&title, &path, filter.as_ref().map(|(patterns, description)| (patterns.as_slice(), *description)))
+    })
+}
+
+/// Non-blocking variant of [`select_folder_dialog`].
+pub fn select_folder_dialog_async(title: &str, path: &str) -> DialogHandle<Option<String>> {
+    let title = title.to_owned();
+    let path = path.to_owned();
+
+    DialogHandle::spawn(move || select_folder_dialog(&title, &path))
+}
+
+/// Owned counterpart of [`DefaultColorValue`] so a default color can be
+/// moved onto a background thread.
+enum OwnedColorValue {
+    Hex(String),
+    Rgb([u8; 3]),
+}
+
+/// Non-blocking variant of [`color_chooser_dialog`].
+pub fn color_chooser_dialog_async(title: &str, default: DefaultColorValue) -> DialogHandle<Option<(String, [u8; 3])>> {
+    let title = title.to_owned();
+    let default = match default {
+        DefaultColorValue::Hex(hex) => OwnedColorValue::Hex(hex.to_owned()),
+        DefaultColorValue::RGB(rgb) => OwnedColorValue::Rgb(*rgb),
+    };
+
+    DialogHandle::spawn(move || {
+        let default = match &default {
+            OwnedColorValue::Hex(hex) => DefaultColorValue::Hex(hex),
+            OwnedColorValue::Rgb(rgb) => DefaultColorValue::RGB(rgb),
+        };
+
+        color_chooser_dialog(&title, default)
+    })
+}
+
+/// Non-blocking variant of [`list_dialog`].
+///
+/// **NOT AVAILABLE ON WINDOWS**
+#[cfg(not(windows))]
+pub fn list_dialog_async(title: &str, columns: &[&str], cells: Option<&[&str]>) -> DialogHandle<Option<String>> {
+    let title = title.to_owned();
+    let columns: Vec<String> = columns.iter().map(|s| (*s).to_owned()).collect();
+    let cells: Option<Vec<String>> = cells.map(|cells| cells.iter().map(|s| (*s).to_owned()).collect());
+
+    DialogHandle::spawn(move || {
+        let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+        let cells: Option<Vec<&str>> = cells.as_ref().map(|cells| cells.iter().map(String::as_str).collect());
+
+        list_dialog(&title, &columns, cells.as_deref())
+    })
+}
@@ -0,0 +1,213 @@
+//! Fluent builders over the free functions in [`crate`], for callers who would rather not spell
+//! out long positional argument lists — especially `message_box`'s `Option<Icon>`/
+//! `Option<BoxButton>` pair — at every call site. These just accumulate owned fields and
+//! dispatch to the existing implementation functions; unset fields behave exactly as the
+//! defaults those functions already use.
+
+use crate::{
+    message_box, open_file_dialog, open_file_dialog_multi, open_file_dialog_multi_with_filters,
+    open_file_dialog_with_filters, save_file_dialog, save_file_dialog_with_filters, BoxButton,
+    FileFilter, Icon, MessageBox,
+};
+
+/// Builds and shows a [`message_box`] dialog
+#[derive(Debug, Clone)]
+pub struct MessageDialog {
+    title: String,
+    message: String,
+    kind: MessageBox,
+    icon: Option<Icon>,
+    default_button: Option<BoxButton>,
+}
+
+impl Default for MessageDialog {
+    fn default() -> MessageDialog {
+        MessageDialog { title: String::new(), message: String::new(), kind: MessageBox::Ok, icon: None, default_button: None }
+    }
+}
+
+impl MessageDialog {
+    /// Starts building a message box with an empty title and message and only an `Ok` button
+    pub fn new() -> MessageDialog {
+        MessageDialog::default()
+    }
+
+    /// Sets the dialog title
+    pub fn title(mut self, title: impl Into<String>) -> MessageDialog {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the dialog message
+    pub fn message(mut self, message: impl Into<String>) -> MessageDialog {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets which buttons the dialog offers
+    pub fn kind(mut self, kind: MessageBox) -> MessageDialog {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the icon shown beside the message; defaults to [`Icon::Info`] if never called
+    pub fn icon(mut self, icon: Icon) -> MessageDialog {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Sets which button is focused by default; defaults to [`BoxButton::OkYes`] if never called
+    pub fn default_button(mut self, default_button: BoxButton) -> MessageDialog {
+        self.default_button = Some(default_button);
+        self
+    }
+
+    /// Shows the dialog and returns which button was clicked
+    pub fn show(self) -> BoxButton {
+        message_box(self.kind, &self.title, &self.message, self.icon, self.default_button)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileDialogMode {
+    Open,
+    Save,
+}
+
+/// Builds and shows an open or save file dialog
+#[derive(Debug, Clone, Default)]
+pub struct FileDialog {
+    title: String,
+    path: String,
+    filters: Vec<FileFilter>,
+    multiple: bool,
+    mode: Option<FileDialogMode>,
+}
+
+impl FileDialog {
+    /// Starts building a file dialog with an empty title, an empty starting path, and no filters
+    pub fn new() -> FileDialog {
+        FileDialog::default()
+    }
+
+    /// Sets the dialog title
+    pub fn title(mut self, title: impl Into<String>) -> FileDialog {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the starting path
+    pub fn path(mut self, path: impl Into<String>) -> FileDialog {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the named filter groups offered by the dialog; defaults to none, showing all files
+    pub fn filters(mut self, filters: impl IntoIterator<Item = FileFilter>) -> FileDialog {
+        self.filters = filters.into_iter().collect();
+        self
+    }
+
+    /// Allows selecting more than one file; only meaningful for [`Self::open`] dialogs.
+    /// Defaults to `false`.
+    pub fn multiple(mut self, multiple: bool) -> FileDialog {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Configures this as an open file dialog; call [`Self::show`] to display it
+    pub fn open(mut self) -> FileDialog {
+        self.mode = Some(FileDialogMode::Open);
+        self
+    }
+
+    /// Configures this as a save file dialog; call [`Self::show`] to display it
+    pub fn save(mut self) -> FileDialog {
+        self.mode = Some(FileDialogMode::Save);
+        self
+    }
+
+    /// Shows the configured dialog and returns the chosen path(s)
+    ///
+    /// # Panics
+    ///
+    /// Panics if neither [`Self::open`] nor [`Self::save`] was called.
+    pub fn show(self) -> FileDialogResult {
+        let mode = self.mode.expect("FileDialog::show called without open() or save()");
+
+        match mode {
+            FileDialogMode::Save if self.filters.is_empty() => FileDialogResult::Single(save_file_dialog(&self.title, &self.path)),
+            FileDialogMode::Save => FileDialogResult::Single(save_file_dialog_with_filters(&self.title, &self.path, &self.filters)),
+
+            FileDialogMode::Open if self.multiple && self.filters.is_empty() => {
+                FileDialogResult::Multiple(open_file_dialog_multi(&self.title, &self.path, None))
+            }
+            FileDialogMode::Open if self.multiple => {
+                FileDialogResult::Multiple(open_file_dialog_multi_with_filters(&self.title, &self.path, &self.filters))
+            }
+            FileDialogMode::Open if self.filters.is_empty() => FileDialogResult::Single(open_file_dialog(&self.title, &self.path, None)),
+            FileDialogMode::Open => FileDialogResult::Single(open_file_dialog_with_filters(&self.title, &self.path, &self.filters)),
+        }
+    }
+}
+
+/// Result of [`FileDialog::show`]; which variant comes back depends on whether
+/// [`FileDialog::multiple`] was set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDialogResult {
+    /// A single path, from a save dialog or a non-multi-select open dialog
+    Single(Option<String>),
+    /// Zero or more paths, from a multi-select open dialog
+    Multiple(Option<Vec<String>>),
+}
+
+impl FileDialogResult {
+    /// Returns the single chosen path, collapsing a multi-select result to its first entry
+    pub fn single(self) -> Option<String> {
+        match self {
+            FileDialogResult::Single(path) => path,
+            FileDialogResult::Multiple(paths) => paths.and_then(|paths| paths.into_iter().next()),
+        }
+    }
+
+    /// Returns the chosen paths, wrapping a single-select result in a one-element `Vec`
+    pub fn multiple(self) -> Option<Vec<String>> {
+        match self {
+            FileDialogResult::Multiple(paths) => paths,
+            FileDialogResult::Single(path) => path.map(|path| vec![path]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_passes_through_a_single_result() {
+        assert_eq!(FileDialogResult::Single(Some("a.txt".to_owned())).single(), Some("a.txt".to_owned()));
+        assert_eq!(FileDialogResult::Single(None).single(), None);
+    }
+
+    #[test]
+    fn single_collapses_a_multiple_result_to_its_first_entry() {
+        let result = FileDialogResult::Multiple(Some(vec!["a.txt".to_owned(), "b.txt".to_owned()]));
+        assert_eq!(result.single(), Some("a.txt".to_owned()));
+
+        assert_eq!(FileDialogResult::Multiple(Some(vec![])).single(), None);
+        assert_eq!(FileDialogResult::Multiple(None).single(), None);
+    }
+
+    #[test]
+    fn multiple_passes_through_a_multiple_result() {
+        let paths = vec!["a.txt".to_owned(), "b.txt".to_owned()];
+        assert_eq!(FileDialogResult::Multiple(Some(paths.clone())).multiple(), Some(paths));
+        assert_eq!(FileDialogResult::Multiple(None).multiple(), None);
+    }
+
+    #[test]
+    fn multiple_wraps_a_single_result_in_a_one_element_vec() {
+        assert_eq!(FileDialogResult::Single(Some("a.txt".to_owned())).multiple(), Some(vec!["a.txt".to_owned()]));
+        assert_eq!(FileDialogResult::Single(None).multiple(), None);
+    }
+}
@@ -52,6 +52,11 @@ use std::ffi::{CStr, CString};
 use std::ptr;
 
 pub mod ffi;
+pub mod async_dialog;
+pub mod builder;
+
+#[cfg(all(feature = "xdg-portal", any(target_os = "linux", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+pub mod xdg_portal;
 
 /// Type of message box to display
 #[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
@@ -164,6 +169,15 @@ pub fn password_box(title: &str, message: &str) -> Option<String> {
 }
 
 fn save_file_dialog_impl(title: &str, path: &str, filter: Option<(&[&str], &str)>) -> Option<String> {
+    #[cfg(all(feature = "xdg-portal", any(target_os = "linux", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+    if xdg_portal::backend() == xdg_portal::DialogBackend::XdgPortal {
+        // A portal failure (no session bus, portal absent, ...) falls back to the native FFI
+        // path below rather than being reported as a user cancel.
+        if let Ok(result) = xdg_portal::save_file_dialog_portal(title, path, filter) {
+            return result;
+        }
+    }
+
     let save_dialog_title = CString::new(title).unwrap();
     let save_dialog_path = CString::new(path).unwrap();
     let save_dialog_des = CString::new(filter.map_or("", |f| f.1)).unwrap();
@@ -195,7 +209,112 @@ pub fn save_file_dialog(title: &str, path: &str) -> Option<String> {
     save_file_dialog_impl(title, path, None)
 }
 
+/// Behavioral options for the file dialog `_with_options` variants, inspired by FLTK's
+/// `Fl_Native_File_Chooser` option flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileDialogOptions(u8);
+
+impl FileDialogOptions {
+    /// No options set
+    pub const NONE: FileDialogOptions = FileDialogOptions(0);
+    /// Prompt the user for confirmation before overwriting an existing file. Save dialogs only.
+    ///
+    /// tinyfiledialogs' native GUI backends (the Windows and macOS save panels, as well as
+    /// `zenity`/`kdialog` on Linux) already prompt before overwriting, so this flag only takes
+    /// effect on targets other than Windows and macOS (Linux, the BSDs, ...), where it backstops
+    /// backends without a built-in prompt (e.g. the text-mode fallback used when no GUI tool is
+    /// found). On Windows and macOS it is a
+    /// no-op, to avoid stacking a second confirmation on top of the OS's own.
+    pub const SAVE_AS_CONFIRM: FileDialogOptions = FileDialogOptions(1 << 0);
+    /// Show a "new folder" button in the dialog.
+    ///
+    /// Not implemented: `tinyfiledialogs` does not expose this through its C API, so it
+    /// currently has no effect.
+    pub const NEW_FOLDER: FileDialogOptions = FileDialogOptions(1 << 1);
+    /// Show a preview of the selected file.
+    ///
+    /// Not implemented: `tinyfiledialogs` does not expose this through its C API, so it
+    /// currently has no effect.
+    pub const PREVIEW: FileDialogOptions = FileDialogOptions(1 << 2);
+    /// If the path the user typed or picked has no extension, append the extension of the
+    /// filter's first pattern to it (e.g. `txt` for `*.txt`). Save dialogs only.
+    pub const USE_FILTER_EXT: FileDialogOptions = FileDialogOptions(1 << 3);
+
+    /// Returns whether every flag set in `other` is also set in `self`
+    pub fn contains(self, other: FileDialogOptions) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for FileDialogOptions {
+    fn default() -> FileDialogOptions {
+        FileDialogOptions::NONE
+    }
+}
+
+impl std::ops::BitOr for FileDialogOptions {
+    type Output = FileDialogOptions;
+
+    fn bitor(self, rhs: FileDialogOptions) -> FileDialogOptions {
+        FileDialogOptions(self.0 | rhs.0)
+    }
+}
+
+/// Appends the first filter pattern's extension to `path` if `path` has none of its own
+fn append_filter_extension(path: String, filter: Option<(&[&str], &str)>) -> String {
+    if std::path::Path::new(&path).extension().is_some() {
+        return path;
+    }
+
+    let extension = match filter {
+        Some((patterns, _)) => patterns.first().map(|pattern| pattern.trim_start_matches("*.")),
+        None => None,
+    };
+
+    match extension {
+        Some(extension) if !extension.is_empty() && extension != "*" => format!("{}.{}", path, extension),
+        _ => path,
+    }
+}
+
+/// Display a save file dialog honoring the given [`FileDialogOptions`]
+pub fn save_file_dialog_with_options(title: &str, path: &str, filter: Option<(&[&str], &str)>, options: FileDialogOptions) -> Option<String> {
+    loop {
+        let result = save_file_dialog_impl(title, path, filter)?;
+        let result = if options.contains(FileDialogOptions::USE_FILTER_EXT) {
+            append_filter_extension(result, filter)
+        } else {
+            result
+        };
+
+        #[cfg(any(windows, target_os = "macos"))]
+        let confirm_in_rust = false;
+        #[cfg(not(any(windows, target_os = "macos")))]
+        let confirm_in_rust = options.contains(FileDialogOptions::SAVE_AS_CONFIRM);
+
+        if confirm_in_rust && std::path::Path::new(&result).exists() {
+            let message = format!("{} already exists.\nDo you want to replace it?", result);
+            let overwrite = message_box(MessageBox::YesNo, title, &message, Some(Icon::Warning), Some(BoxButton::CancelNo));
+
+            if overwrite != BoxButton::OkYes {
+                continue;
+            }
+        }
+
+        return Some(result);
+    }
+}
+
 fn open_file_dialog_impl(title: &str, path: &str, filter: Option<(&[&str], &str)>, multi: bool) -> Option<Vec<String>> {
+    #[cfg(all(feature = "xdg-portal", any(target_os = "linux", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+    if xdg_portal::backend() == xdg_portal::DialogBackend::XdgPortal {
+        // A portal failure (no session bus, portal absent, ...) falls back to the native FFI
+        // path below rather than being reported as a user cancel.
+        if let Ok(result) = xdg_portal::open_file_dialog_portal(title, path, filter, multi) {
+            return result;
+        }
+    }
+
     let open_dialog_title = CString::new(title).unwrap();
     let open_dialog_path = CString::new(path).unwrap();
     let open_dialog_des = CString::new(filter.map_or("", |f| f.1)).unwrap();
@@ -230,8 +349,75 @@ pub fn open_file_dialog_multi(title: &str, path: &str, filter: Option<(&[&str],
     open_file_dialog_impl(title, path, filter, true)
 }
 
+/// Display an open file dialog honoring the given [`FileDialogOptions`]
+///
+/// `SAVE_AS_CONFIRM` and `USE_FILTER_EXT` are save-dialog behaviors and have no effect here.
+pub fn open_file_dialog_with_options(title: &str, path: &str, filter: Option<(&[&str], &str)>, _options: FileDialogOptions) -> Option<String> {
+    open_file_dialog_impl(title, path, filter, false).and_then(|v| v.into_iter().next())
+}
+
+/// A named group of file extension patterns, e.g. `Images (*.png *.jpg)`
+///
+/// Several groups can be offered at once via the `_with_filters` dialog variants, unlike the
+/// single `(patterns, description)` tuple the rest of this crate's functions take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    /// Display name for this group, e.g. `"Images"`
+    pub name: String,
+    /// Patterns belonging to this group, e.g. `["*.png", "*.jpg"]`
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// Creates a new filter group from a name and a list of extension patterns
+    pub fn new<S: Into<String>, E: Into<String>>(name: S, extensions: impl IntoIterator<Item = E>) -> FileFilter {
+        FileFilter { name: name.into(), extensions: extensions.into_iter().map(Into::into).collect() }
+    }
+}
+
+/// Flattens several named filter groups into the single `(patterns, description)` pair the
+/// underlying `tinyfd_*FileDialog` calls take, synthesizing a combined description from the
+/// group names, e.g. `"Images (*.png *.jpg), Documents (*.pdf)"`
+fn flatten_filters(filters: &[FileFilter]) -> (Vec<&str>, String) {
+    let patterns = filters.iter().flat_map(|filter| filter.extensions.iter().map(String::as_str)).collect();
+
+    let description = filters.iter()
+        .map(|filter| format!("{} ({})", filter.name, filter.extensions.join(" ")))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    (patterns, description)
+}
+
+/// Display a save file dialog offering several named filter groups
+pub fn save_file_dialog_with_filters(title: &str, path: &str, filters: &[FileFilter]) -> Option<String> {
+    let (patterns, description) = flatten_filters(filters);
+    save_file_dialog_impl(title, path, Some((&patterns, &description)))
+}
+
+/// Display an open file dialog for a single file, offering several named filter groups
+pub fn open_file_dialog_with_filters(title: &str, path: &str, filters: &[FileFilter]) -> Option<String> {
+    let (patterns, description) = flatten_filters(filters);
+    open_file_dialog_impl(title, path, Some((&patterns, &description)), false).and_then(|v| v.into_iter().next())
+}
+
+/// Display an open file dialog with support for multiple files, offering several named filter groups
+pub fn open_file_dialog_multi_with_filters(title: &str, path: &str, filters: &[FileFilter]) -> Option<Vec<String>> {
+    let (patterns, description) = flatten_filters(filters);
+    open_file_dialog_impl(title, path, Some((&patterns, &description)), true)
+}
+
 /// Display a dialog for selecting filesystem folders
 pub fn select_folder_dialog(title: &str, path: &str) -> Option<String> {
+    #[cfg(all(feature = "xdg-portal", any(target_os = "linux", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+    if xdg_portal::backend() == xdg_portal::DialogBackend::XdgPortal {
+        // A portal failure (no session bus, portal absent, ...) falls back to the native FFI
+        // path below rather than being reported as a user cancel.
+        if let Ok(result) = xdg_portal::select_folder_dialog_portal(title, path) {
+            return result;
+        }
+    }
+
     let select_folder_title = CString::new(title).unwrap();
     let select_folder_path = CString::new(path).unwrap();
 
@@ -294,8 +480,7 @@ pub enum DefaultColorValue<'a> {
     RGB(&'a [u8; 3]),
 }
 
-/// Displays the system color chooser dialog
-pub fn color_chooser_dialog(title: &str, default: DefaultColorValue) -> Option<(String, [u8; 3])> {
+fn color_chooser_dialog_impl(title: &str, default: DefaultColorValue) -> Option<(String, [u8; 3])> {
     let color_title = CString::new(title).unwrap();
 
     let rubbish = [0, 0, 0];
@@ -317,4 +502,141 @@ pub fn color_chooser_dialog(title: &str, default: DefaultColorValue) -> Option<(
     if !result.is_null() {
         unsafe { Some((CStr::from_ptr(result).to_string_lossy().into_owned(), color_result_rgb)) }
     } else { None }
+}
+
+/// Displays the system color chooser dialog
+pub fn color_chooser_dialog(title: &str, default: DefaultColorValue) -> Option<(String, [u8; 3])> {
+    color_chooser_dialog_impl(title, default)
+}
+
+/// Representation to return the picked color in, modeled on FLTK's color chooser modes
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum ColorMode {
+    /// RGB channels as floats in `0.0..=1.0`
+    Rgb,
+    /// RGB channels as `u8`s (8-bit unsigned integers) in `0..=255`
+    Byte,
+    /// `#RRGGBB` hex string
+    Hex,
+    /// Hue (`0.0..360.0`), saturation and value (both `0.0..=1.0`)
+    Hsv,
+}
+
+/// A color picked from [`color_chooser_dialog_with_mode`], in the representation requested by its `mode`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Color {
+    /// RGB channels as floats in `0.0..=1.0`
+    Rgb([f32; 3]),
+    /// RGB channels as `u8`s (8-bit unsigned integers) in `0..=255`
+    Byte([u8; 3]),
+    /// `#RRGGBB` hex string
+    Hex(String),
+    /// Hue (`0.0..360.0`), saturation and value (both `0.0..=1.0`)
+    Hsv(f32, f32, f32),
+}
+
+/// Converts an RGB triplet to hue/saturation/value, using the standard max/min/delta
+/// conversion. Hue is undefined for gray (zero saturation) and reported as `0.0`.
+fn rgb_to_hsv(rgb: [u8; 3]) -> (f32, f32, f32) {
+    let r = rgb[0] as f32 / 255.0;
+    let g = rgb[1] as f32 / 255.0;
+    let b = rgb[2] as f32 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * (((g - b) / delta).rem_euclid(6.0))
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+    (hue, saturation, max)
+}
+
+/// Displays the system color chooser dialog, returning the picked color in the representation
+/// requested by `mode` rather than the combined hex/RGB pair `color_chooser_dialog` returns
+pub fn color_chooser_dialog_with_mode(title: &str, default: DefaultColorValue, mode: ColorMode) -> Option<Color> {
+    let (hex, rgb) = color_chooser_dialog_impl(title, default)?;
+
+    Some(match mode {
+        ColorMode::Rgb => Color::Rgb([rgb[0] as f32 / 255.0, rgb[1] as f32 / 255.0, rgb[2] as f32 / 255.0]),
+        ColorMode::Byte => Color::Byte(rgb),
+        ColorMode::Hex => Color::Hex(hex),
+        ColorMode::Hsv => {
+            let (h, s, v) = rgb_to_hsv(rgb);
+            Color::Hsv(h, s, v)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb_to_hsv_gray_has_zero_hue_and_saturation() {
+        assert_eq!(rgb_to_hsv([128, 128, 128]), (0.0, 0.0, 128.0 / 255.0));
+        assert_eq!(rgb_to_hsv([0, 0, 0]), (0.0, 0.0, 0.0));
+        assert_eq!(rgb_to_hsv([255, 255, 255]), (0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn rgb_to_hsv_primary_colors() {
+        let (h, s, v) = rgb_to_hsv([255, 0, 0]);
+        assert_eq!((h, s, v), (0.0, 1.0, 1.0));
+
+        let (h, s, v) = rgb_to_hsv([0, 255, 0]);
+        assert_eq!((h, s, v), (120.0, 1.0, 1.0));
+
+        let (h, s, v) = rgb_to_hsv([0, 0, 255]);
+        assert_eq!((h, s, v), (240.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn append_filter_extension_appends_when_missing() {
+        assert_eq!(append_filter_extension("out".to_owned(), Some((&["*.txt"], "Text"))), "out.txt");
+    }
+
+    #[test]
+    fn append_filter_extension_leaves_existing_extension_alone() {
+        assert_eq!(append_filter_extension("out.md".to_owned(), Some((&["*.txt"], "Text"))), "out.md");
+    }
+
+    #[test]
+    fn append_filter_extension_handles_multi_dot_extensions() {
+        assert_eq!(append_filter_extension("archive".to_owned(), Some((&["*.tar.gz"], "Archive"))), "archive.tar.gz");
+    }
+
+    #[test]
+    fn append_filter_extension_ignores_wildcard_and_missing_filters() {
+        assert_eq!(append_filter_extension("out".to_owned(), Some((&["*.*"], "All"))), "out");
+        assert_eq!(append_filter_extension("out".to_owned(), Some((&[], "All"))), "out");
+        assert_eq!(append_filter_extension("out".to_owned(), None), "out");
+    }
+
+    #[test]
+    fn flatten_filters_combines_groups_into_one_pattern_list_and_description() {
+        let filters = vec![FileFilter::new("Images", ["*.png", "*.jpg"]), FileFilter::new("Documents", ["*.pdf"])];
+
+        let (patterns, description) = flatten_filters(&filters);
+
+        assert_eq!(patterns, vec!["*.png", "*.jpg", "*.pdf"]);
+        assert_eq!(description, "Images (*.png *.jpg), Documents (*.pdf)");
+    }
+
+    #[test]
+    fn flatten_filters_empty_is_empty() {
+        let (patterns, description) = flatten_filters(&[]);
+
+        assert!(patterns.is_empty());
+        assert_eq!(description, "");
+    }
 }
\ No newline at end of file
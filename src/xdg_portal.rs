@@ -0,0 +1,301 @@
+//! Rust-side file dialog backend that talks to the
+//! `org.freedesktop.portal.FileChooser` D-Bus interface directly, instead of
+//! going through the bundled C library's `zenity`/`kdialog`/`xterm`
+//! shell-outs. The portal path works inside Flatpak/Snap sandboxes and on
+//! headless-portal desktops where spawning those helper binaries fails.
+//!
+//! Only compiled with the `xdg-portal` Cargo feature, and only takes effect
+//! on Linux and the BSDs. The native FFI path remains the default; opt in
+//! per-process with [`set_backend`].
+
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use zbus::blocking::Connection;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+const BUS_NAME: &str = "org.freedesktop.portal.Desktop";
+const OBJECT_PATH: &str = "/org/freedesktop/portal/desktop";
+const FILE_CHOOSER_INTERFACE: &str = "org.freedesktop.portal.FileChooser";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+
+/// Which implementation backs the file dialog functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogBackend {
+    /// The bundled `tinyfiledialogs` C library (the crate's historical behavior).
+    Native,
+    /// The `org.freedesktop.portal.FileChooser` D-Bus interface.
+    XdgPortal,
+}
+
+static FORCE_PORTAL: AtomicBool = AtomicBool::new(false);
+
+/// Selects which backend `open_file_dialog*`, `save_file_dialog`, and
+/// `select_folder_dialog` use for the rest of the process's lifetime.
+///
+/// Defaults to [`DialogBackend::Native`]. Has no effect unless the
+/// `xdg-portal` feature is enabled and the target is Linux or a BSD.
+pub fn set_backend(backend: DialogBackend) {
+    FORCE_PORTAL.store(backend == DialogBackend::XdgPortal, Ordering::SeqCst);
+}
+
+/// Returns the backend currently selected by [`set_backend`].
+pub fn backend() -> DialogBackend {
+    if FORCE_PORTAL.load(Ordering::SeqCst) { DialogBackend::XdgPortal } else { DialogBackend::Native }
+}
+
+/// A D-Bus call to the portal failed, or it returned a response this crate
+/// doesn't know how to interpret.
+#[derive(Debug)]
+pub struct PortalError(zbus::Error);
+
+impl std::fmt::Display for PortalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "xdg-desktop-portal file chooser request failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for PortalError {}
+
+impl From<zbus::Error> for PortalError {
+    fn from(err: zbus::Error) -> PortalError {
+        PortalError(err)
+    }
+}
+
+impl From<zbus::zvariant::Error> for PortalError {
+    fn from(err: zbus::zvariant::Error) -> PortalError {
+        PortalError(zbus::Error::Variant(err))
+    }
+}
+
+/// Tag for a `filters` entry matching a glob pattern, per the
+/// `org.freedesktop.portal.FileChooser` `filters: a(sa(us))` signature (the
+/// other tag, `1`, is a MIME type, which this crate never emits).
+const FILTER_KIND_GLOB: u32 = 0;
+
+fn filter_options(filter: Option<(&[&str], &str)>) -> Vec<(String, Vec<(u32, String)>)> {
+    match filter {
+        Some((patterns, description)) => {
+            let globs = patterns.iter().map(|pattern| (FILTER_KIND_GLOB, (*pattern).to_owned())).collect();
+            vec![(description.to_owned(), globs)]
+        }
+        None => vec![],
+    }
+}
+
+/// The portal's `current_folder` option is a C string (`ay`), NUL terminator included.
+fn current_folder_bytes(path: &str) -> Vec<u8> {
+    let mut bytes = path.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+/// Splits a save-dialog path like `save_file_dialog`'s (a suggested full file path, e.g.
+/// `"/home/user/password.txt"`) into the portal's `current_folder` directory hint and
+/// `current_name` suggested file name, since `current_folder` is a directory, not a file.
+fn split_save_path(path: &str) -> (String, Option<String>) {
+    let path = std::path::Path::new(path);
+
+    match path.file_name() {
+        Some(file_name) => {
+            let directory = path.parent().map(|parent| parent.to_string_lossy().into_owned()).unwrap_or_default();
+            (directory, Some(file_name.to_string_lossy().into_owned()))
+        }
+        None => (path.to_string_lossy().into_owned(), None),
+    }
+}
+
+/// Decodes the percent-escapes the portal uses in returned `uris`, e.g. `%20` -> ` `.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+            if let Some(byte) = hex {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn uris_from_response(results: &HashMap<String, OwnedValue>) -> Option<Vec<String>> {
+    let uris: Vec<String> = results.get("uris")?.clone().try_into().ok()?;
+
+    Some(uris.into_iter().map(|uri| percent_decode(uri.strip_prefix("file://").unwrap_or(&uri))).collect())
+}
+
+fn single_uri_from_response(results: &HashMap<String, OwnedValue>) -> Option<String> {
+    uris_from_response(results).and_then(|mut uris| if uris.is_empty() { None } else { Some(uris.remove(0)) })
+}
+
+static NEXT_HANDLE_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// A portal `handle_token` unique to this process, so the request object path we subscribe to
+/// before issuing the call is the same one the portal will emit `Response` on.
+fn unique_handle_token() -> String {
+    format!("tfd{}", NEXT_HANDLE_TOKEN.fetch_add(1, Ordering::SeqCst))
+}
+
+/// Subscribes to the `Response` signal for a not-yet-issued request *before* that request is
+/// made, so a portal that responds immediately can't fire `Response` before anything is
+/// listening. Per the portal spec, the request's object path is derived from the caller's own
+/// unique bus name and the `handle_token` passed in the call's options.
+fn subscribe_request<'c>(connection: &'c Connection, handle_token: &str) -> Result<zbus::blocking::SignalIterator<'c>, PortalError> {
+    let sender = connection
+        .unique_name()
+        .ok_or_else(|| PortalError(zbus::Error::Failure("session bus connection has no unique name".into())))?
+        .trim_start_matches(':')
+        .replace('.', "_");
+
+    let request_path = format!("/org/freedesktop/portal/desktop/request/{}/{}", sender, handle_token);
+    let request_path = ObjectPath::try_from(request_path)?;
+
+    let proxy = zbus::blocking::Proxy::new(connection, BUS_NAME, request_path, REQUEST_INTERFACE)?;
+
+    Ok(proxy.receive_signal("Response")?)
+}
+
+/// Issues a `FileChooser` method call and waits for its `Response`, subscribing to the response
+/// signal before the call is made so a fast portal can't respond before anything is listening.
+fn call_and_await_response(
+    connection: &Connection,
+    method: &str,
+    title: &str,
+    mut options: HashMap<&str, Value>,
+) -> Result<(u32, HashMap<String, OwnedValue>), PortalError> {
+    let handle_token = unique_handle_token();
+    let mut signals = subscribe_request(connection, &handle_token)?;
+    // Moved in (not borrowed) so its `Value` doesn't tie this map to `handle_token`'s lifetime,
+    // which ends at the close of this function.
+    options.insert("handle_token", Value::from(handle_token));
+
+    let proxy = zbus::blocking::Proxy::new(connection, BUS_NAME, OBJECT_PATH, FILE_CHOOSER_INTERFACE)?;
+    let _request: OwnedObjectPath = proxy.call(method, &("", title, options))?;
+
+    let message = signals.next().ok_or_else(|| PortalError(zbus::Error::Failure("portal closed without a Response".into())))?;
+
+    Ok(message.body()?)
+}
+
+/// Portal-backed equivalent of [`crate::open_file_dialog`] /
+/// [`crate::open_file_dialog_multi`].
+pub fn open_file_dialog_portal(title: &str, path: &str, filter: Option<(&[&str], &str)>, multi: bool) -> Result<Option<Vec<String>>, PortalError> {
+    let connection = Connection::session()?;
+
+    let mut options: HashMap<&str, Value> = HashMap::new();
+    options.insert("multiple", Value::from(multi));
+    options.insert("current_folder", Value::from(current_folder_bytes(path)));
+    let filters = filter_options(filter);
+    if !filters.is_empty() {
+        options.insert("filters", Value::from(filters));
+    }
+
+    let (response_code, results) = call_and_await_response(&connection, "OpenFile", title, options)?;
+
+    if response_code != 0 {
+        return Ok(None);
+    }
+
+    Ok(uris_from_response(&results))
+}
+
+/// Portal-backed equivalent of [`crate::save_file_dialog`] /
+/// [`crate::save_file_dialog_with_filter`].
+pub fn save_file_dialog_portal(title: &str, path: &str, filter: Option<(&[&str], &str)>) -> Result<Option<String>, PortalError> {
+    let connection = Connection::session()?;
+
+    let (directory, file_name) = split_save_path(path);
+
+    let mut options: HashMap<&str, Value> = HashMap::new();
+    options.insert("current_folder", Value::from(current_folder_bytes(&directory)));
+    if let Some(file_name) = file_name {
+        options.insert("current_name", Value::from(file_name));
+    }
+    let filters = filter_options(filter);
+    if !filters.is_empty() {
+        options.insert("filters", Value::from(filters));
+    }
+
+    let (response_code, results) = call_and_await_response(&connection, "SaveFile", title, options)?;
+
+    if response_code != 0 {
+        return Ok(None);
+    }
+
+    Ok(single_uri_from_response(&results))
+}
+
+/// Portal-backed equivalent of [`crate::select_folder_dialog`].
+pub fn select_folder_dialog_portal(title: &str, path: &str) -> Result<Option<String>, PortalError> {
+    let connection = Connection::session()?;
+
+    let mut options: HashMap<&str, Value> = HashMap::new();
+    options.insert("directory", Value::from(true));
+    options.insert("current_folder", Value::from(current_folder_bytes(path)));
+
+    let (response_code, results) = call_and_await_response(&connection, "OpenFile", title, options)?;
+
+    if response_code != 0 {
+        return Ok(None);
+    }
+
+    Ok(single_uri_from_response(&results))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_decode_decodes_escapes() {
+        assert_eq!(percent_decode("my%20file.txt"), "my file.txt");
+        assert_eq!(percent_decode("%2Fhome%2Fuser"), "/home/user");
+    }
+
+    #[test]
+    fn percent_decode_leaves_unescaped_text_alone() {
+        assert_eq!(percent_decode("/home/user/plain.txt"), "/home/user/plain.txt");
+        assert_eq!(percent_decode(""), "");
+    }
+
+    #[test]
+    fn percent_decode_tolerates_trailing_or_malformed_escapes() {
+        assert_eq!(percent_decode("truncated%2"), "truncated%2");
+        assert_eq!(percent_decode("truncated%"), "truncated%");
+        assert_eq!(percent_decode("bad%zzescape"), "bad%zzescape");
+    }
+
+    #[test]
+    fn filter_options_tags_patterns_as_glob() {
+        let filters = filter_options(Some((&["*.png", "*.jpg"], "Images")));
+
+        assert_eq!(filters, vec![("Images".to_owned(), vec![(FILTER_KIND_GLOB, "*.png".to_owned()), (FILTER_KIND_GLOB, "*.jpg".to_owned())])]);
+    }
+
+    #[test]
+    fn current_folder_bytes_is_nul_terminated() {
+        assert_eq!(current_folder_bytes("/home/user"), b"/home/user\0");
+    }
+
+    #[test]
+    fn split_save_path_separates_directory_from_file_name() {
+        assert_eq!(split_save_path("/home/user/password.txt"), ("/home/user".to_owned(), Some("password.txt".to_owned())));
+        assert_eq!(split_save_path("password.txt"), ("".to_owned(), Some("password.txt".to_owned())));
+        // A trailing slash doesn't make a path directory-only to `Path` -- it still yields a
+        // `file_name` for the last component, same as `/home/user`.
+        assert_eq!(split_save_path("/home/user/"), ("/home".to_owned(), Some("user".to_owned())));
+    }
+}